@@ -50,9 +50,11 @@ use surf::http::StatusCode;
 use thiserror::Error;
 use url::Url;
 
+pub mod sbom;
+
 /// Package identifies the code library or command that
 /// is potentially affected by a particular vulnerability.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Package {
     /// The name of the package or dependency.
     pub name: String,
@@ -68,6 +70,142 @@ pub struct Package {
     pub purl: Option<String>,
 }
 
+impl Package {
+    ///
+    /// Derive the canonical [Package URL](https://github.com/package-url/purl-spec)
+    /// for this package from its `name` and `ecosystem`, e.g. `pkg:cargo/osv` for a
+    /// [`CratesIO`](Ecosystem::CratesIO) package named `osv`.
+    ///
+    /// Ecosystems that don't have a well-known purl type (for example
+    /// [`OssFuzz`](Ecosystem::OssFuzz)) fall back to the generic `pkg:generic/` type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osv::{Ecosystem, Package};
+    ///
+    /// let pkg = Package {
+    ///     name: "jinja2".to_string(),
+    ///     ecosystem: Ecosystem::PyPI,
+    ///     purl: None,
+    /// };
+    /// assert_eq!(pkg.purl(), "pkg:pypi/jinja2");
+    /// ```
+    pub fn purl(&self) -> String {
+        let purl_type = match self.ecosystem {
+            Ecosystem::CratesIO => "cargo",
+            Ecosystem::Npm => "npm",
+            Ecosystem::PyPI => "pypi",
+            Ecosystem::RubyGems => "gem",
+            Ecosystem::Go => "golang",
+            Ecosystem::Maven => "maven",
+            Ecosystem::NuGet => "nuget",
+            Ecosystem::Packagist => "composer",
+            Ecosystem::Hex => "hex",
+            Ecosystem::Pub => "pub",
+            _ => "generic",
+        };
+
+        // Maven package names are `groupId:artifactId`; the purl spec uses
+        // the group ID as the namespace, separated from the name by `/`
+        // rather than `:`.
+        if let Ecosystem::Maven = self.ecosystem {
+            if let Some((group_id, artifact_id)) = self.name.split_once(':') {
+                return format!(
+                    "pkg:maven/{}/{}",
+                    encode_purl_component(group_id),
+                    encode_purl_component(artifact_id)
+                );
+            }
+        }
+
+        format!("pkg:{}/{}", purl_type, encode_purl_component(&self.name))
+    }
+}
+
+/// Percent-encode a purl path component, leaving the characters the
+/// [package-url spec](https://github.com/package-url/purl-spec) treats as safe
+/// (including `/`, used as the namespace separator in scoped package names)
+/// untouched.
+pub(crate) fn encode_purl_component(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                out.push(b as char)
+            }
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+/// Percent-decode a purl path component, the inverse of [`encode_purl_component`].
+fn decode_purl_component(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 3 <= bytes.len() {
+            if let Ok(byte) = u8::from_str_radix(&s[i + 1..i + 3], 16) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+/// The inverse of the purl type derivation in [`Package::purl`](Package::purl):
+/// map a purl `type` back to the [`Ecosystem`](Ecosystem) osv expects it to be
+/// queried under.
+pub(crate) fn ecosystem_from_purl_type(purl_type: &str) -> Option<Ecosystem> {
+    match purl_type {
+        "cargo" => Some(Ecosystem::CratesIO),
+        "npm" => Some(Ecosystem::Npm),
+        "pypi" => Some(Ecosystem::PyPI),
+        "gem" => Some(Ecosystem::RubyGems),
+        "golang" => Some(Ecosystem::Go),
+        "maven" => Some(Ecosystem::Maven),
+        "nuget" => Some(Ecosystem::NuGet),
+        "composer" => Some(Ecosystem::Packagist),
+        "hex" => Some(Ecosystem::Hex),
+        "pub" => Some(Ecosystem::Pub),
+        _ => None,
+    }
+}
+
+/// Split a purl into the [`Ecosystem`](Ecosystem) and package name osv expects, and
+/// its version qualifier if it has one, e.g.
+/// `"pkg:npm/%40babel/core@7.0.0"` -> `(Ecosystem::Npm, "@babel/core", Some("7.0.0"))`
+/// and `"pkg:maven/org.springframework/spring-core"` ->
+/// `(Ecosystem::Maven, "org.springframework:spring-core", None)`.
+///
+/// This mirrors [`Package::purl`](Package::purl) in reverse: purl path components are
+/// percent-decoded, and a Maven `namespace/name` is rejoined with `:` since
+/// that's the format osv's Maven ecosystem expects.
+pub(crate) fn parse_purl(purl: &str) -> Option<(Ecosystem, String, Option<String>)> {
+    let rest = purl.strip_prefix("pkg:")?;
+    let rest = rest.split(['?', '#']).next()?;
+    let (path, version) = match rest.rsplit_once('@') {
+        Some((path, version)) => (path, Some(decode_purl_component(version))),
+        None => (rest, None),
+    };
+    let (purl_type, name_part) = path.split_once('/')?;
+    let ecosystem = ecosystem_from_purl_type(purl_type)?;
+    let decoded = decode_purl_component(name_part);
+    let name = if purl_type == "maven" {
+        let (group_id, artifact_id) = decoded.split_once('/')?;
+        format!("{}:{}", group_id, artifact_id)
+    } else {
+        decoded
+    };
+    Some((ecosystem, name, version))
+}
+
 /// A commit is a full SHA1 Git hash in hex format.
 pub type Commit = String;
 
@@ -76,7 +214,7 @@ pub type Version = String;
 
 /// The package ecosystem that the vulnerabilities in the OSV database
 /// are associated with.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[non_exhaustive]
 pub enum Ecosystem {
     Go,
@@ -102,7 +240,7 @@ pub enum Ecosystem {
 
 /// Type of the affected range supplied. This can be an ecosystem
 /// specific value, semver, or a git commit hash.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum RangeType {
     /// Default for the case where a range type is omitted.
@@ -121,7 +259,7 @@ pub enum RangeType {
 
 /// The event captures information about the how and when
 /// the package was affected by the vulnerability.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 #[non_exhaustive]
 pub enum Event {
@@ -138,7 +276,7 @@ pub enum Event {
 
 /// The range of versions of a package for which
 /// it is affected by the vulnerability.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Range {
     /// The format that the range events are specified in, for
     /// example SEMVER or GIT.
@@ -160,7 +298,7 @@ pub struct Range {
 /// by a particular vulnerability. The affected ranges can include
 /// when the vulnerability was first introduced and also when it
 /// was fixed.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Affected {
     /// The package that is affected by the vulnerability
     pub package: Package,
@@ -174,6 +312,12 @@ pub struct Affected {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub versions: Option<Vec<String>>,
 
+    /// The severity of the vulnerability for this specific package, when it
+    /// differs from the overall severity given at the top level of the
+    /// [`Vulnerability`](Vulnerability).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<Vec<Severity>>,
+
     /// A JSON object that holds any additional information about the
     /// vulnerability as defined by the ecosystem for which the record applies.
     ///
@@ -191,7 +335,7 @@ pub struct Affected {
 /// The type of reference information that has been provided. Examples include
 /// links to the original report, external advisories, or information about the
 /// fix.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 pub enum ReferenceType {
     #[serde(rename = "NONE")]
@@ -205,7 +349,7 @@ pub enum ReferenceType {
 }
 
 /// Reference to additional information about the vulnerability.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Reference {
     /// The type of reference this URL points to.
     #[serde(rename = "type")]
@@ -218,23 +362,40 @@ pub struct Reference {
 
 /// The [`SeverityType`](SeverityType) describes the quantitative scoring method used to rate the
 /// severity of the vulnerability.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum SeverityType {
     /// The severity score was arrived at by using an unspecified
     /// scoring method.
     #[serde(rename = "UNSPECIFIED")]
     Unspecified,
 
+    /// A CVSS vector string representing the unique characteristics and severity of the
+    /// vulnerability using a version of the Common Vulnerability Scoring System notation that is
+    /// >= 2.0 and < 3.0.
+    #[serde(rename = "CVSS_V2")]
+    CVSSv2,
+
     /// A CVSS vector string representing the unique characteristics and severity of the
     /// vulnerability using a version of the Common Vulnerability Scoring System notation that is
     /// >= 3.0 and < 4.0 (e.g.`"CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:C/C:H/I:N/A:N"`).
     #[serde(rename = "CVSS_V3")]
     CVSSv3,
+
+    /// A CVSS vector string representing the unique characteristics and severity of the
+    /// vulnerability using a version of the Common Vulnerability Scoring System notation that is
+    /// >= 4.0 and < 5.0.
+    #[serde(rename = "CVSS_V4")]
+    CVSSv4,
+
+    /// A severity score as defined by Ubuntu's security team, used for vulnerabilities
+    /// affecting the Ubuntu ecosystem.
+    Ubuntu,
 }
 
 /// The type and score used to describe the severity of a vulnerability using one
 /// or more quantitative scoring methods.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Severity {
     /// The severity type property must be a [`SeverityType`](SeverityType), which describes the
     /// quantitative method used to calculate the associated score.
@@ -246,9 +407,267 @@ pub struct Severity {
     pub score: String,
 }
 
+impl Severity {
+    ///
+    /// Parse the `score` field as a CVSS v3.1 vector string, e.g.
+    /// `"CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:C/C:H/I:N/A:N"`, returning the individual
+    /// metrics along with the computed base score.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osv::Severity;
+    /// use osv::SeverityType::CVSSv3;
+    ///
+    /// let severity = Severity {
+    ///     severity_type: CVSSv3,
+    ///     score: "CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:C/C:H/I:N/A:N".to_string(),
+    /// };
+    /// let cvss = severity.parse_cvss().unwrap();
+    /// assert_eq!(cvss.base_score, 6.8);
+    /// ```
+    pub fn parse_cvss(&self) -> Result<CvssV3, ApiError> {
+        CvssV3::parse(&self.score)
+    }
+}
+
+/// The Attack Vector (AV) metric of a CVSS v3.1 vector, reflecting how remote
+/// an attacker can be to exploit the vulnerability.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackVector {
+    Network,
+    Adjacent,
+    Local,
+    Physical,
+}
+
+/// The Attack Complexity (AC) metric of a CVSS v3.1 vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttackComplexity {
+    Low,
+    High,
+}
+
+/// The Privileges Required (PR) metric of a CVSS v3.1 vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrivilegesRequired {
+    None,
+    Low,
+    High,
+}
+
+/// The User Interaction (UI) metric of a CVSS v3.1 vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserInteraction {
+    None,
+    Required,
+}
+
+/// The Scope (S) metric of a CVSS v3.1 vector, reflecting whether the
+/// vulnerability impacts resources beyond its own security scope.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scope {
+    Unchanged,
+    Changed,
+}
+
+/// The level of impact on Confidentiality (C), Integrity (I), or Availability
+/// (A) in a CVSS v3.1 vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiaImpact {
+    None,
+    Low,
+    High,
+}
+
+/// A CVSS v3.1 vector string parsed into its individual metrics, along with
+/// the base score computed from them.
+///
+/// See <https://www.first.org/cvss/v3.1/specification-document> for the
+/// formulas used to derive [`base_score`](CvssV3::base_score).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CvssV3 {
+    pub attack_vector: AttackVector,
+    pub attack_complexity: AttackComplexity,
+    pub privileges_required: PrivilegesRequired,
+    pub user_interaction: UserInteraction,
+    pub scope: Scope,
+    pub confidentiality: CiaImpact,
+    pub integrity: CiaImpact,
+    pub availability: CiaImpact,
+    pub base_score: f64,
+}
+
+impl CvssV3 {
+    /// Parse a CVSS v3.1 vector string and compute its base score.
+    pub fn parse(vector: &str) -> Result<CvssV3, ApiError> {
+        let invalid = || ApiError::InvalidCvssVector(vector.to_string());
+
+        let mut parts = vector.split('/');
+        match parts.next() {
+            Some(prefix) if prefix.starts_with("CVSS:3.") => {}
+            _ => return Err(invalid()),
+        }
+
+        let mut metrics = std::collections::HashMap::new();
+        for part in parts {
+            let (key, value) = part.split_once(':').ok_or_else(invalid)?;
+            metrics.insert(key, value);
+        }
+
+        let get = |key: &str| -> Result<&str, ApiError> {
+            metrics.get(key).copied().ok_or_else(invalid)
+        };
+
+        let attack_vector = match get("AV")? {
+            "N" => AttackVector::Network,
+            "A" => AttackVector::Adjacent,
+            "L" => AttackVector::Local,
+            "P" => AttackVector::Physical,
+            _ => return Err(invalid()),
+        };
+        let attack_complexity = match get("AC")? {
+            "L" => AttackComplexity::Low,
+            "H" => AttackComplexity::High,
+            _ => return Err(invalid()),
+        };
+        let privileges_required = match get("PR")? {
+            "N" => PrivilegesRequired::None,
+            "L" => PrivilegesRequired::Low,
+            "H" => PrivilegesRequired::High,
+            _ => return Err(invalid()),
+        };
+        let user_interaction = match get("UI")? {
+            "N" => UserInteraction::None,
+            "R" => UserInteraction::Required,
+            _ => return Err(invalid()),
+        };
+        let scope = match get("S")? {
+            "U" => Scope::Unchanged,
+            "C" => Scope::Changed,
+            _ => return Err(invalid()),
+        };
+        let confidentiality = match get("C")? {
+            "N" => CiaImpact::None,
+            "L" => CiaImpact::Low,
+            "H" => CiaImpact::High,
+            _ => return Err(invalid()),
+        };
+        let integrity = match get("I")? {
+            "N" => CiaImpact::None,
+            "L" => CiaImpact::Low,
+            "H" => CiaImpact::High,
+            _ => return Err(invalid()),
+        };
+        let availability = match get("A")? {
+            "N" => CiaImpact::None,
+            "L" => CiaImpact::Low,
+            "H" => CiaImpact::High,
+            _ => return Err(invalid()),
+        };
+
+        let base_score = compute_base_score(
+            attack_vector,
+            attack_complexity,
+            privileges_required,
+            user_interaction,
+            scope,
+            confidentiality,
+            integrity,
+            availability,
+        );
+
+        Ok(CvssV3 {
+            attack_vector,
+            attack_complexity,
+            privileges_required,
+            user_interaction,
+            scope,
+            confidentiality,
+            integrity,
+            availability,
+            base_score,
+        })
+    }
+}
+
+fn cia_weight(impact: CiaImpact) -> f64 {
+    match impact {
+        CiaImpact::High => 0.56,
+        CiaImpact::Low => 0.22,
+        CiaImpact::None => 0.0,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn compute_base_score(
+    attack_vector: AttackVector,
+    attack_complexity: AttackComplexity,
+    privileges_required: PrivilegesRequired,
+    user_interaction: UserInteraction,
+    scope: Scope,
+    confidentiality: CiaImpact,
+    integrity: CiaImpact,
+    availability: CiaImpact,
+) -> f64 {
+    let iss = 1.0
+        - (1.0 - cia_weight(confidentiality))
+            * (1.0 - cia_weight(integrity))
+            * (1.0 - cia_weight(availability));
+
+    let impact = match scope {
+        Scope::Unchanged => 6.42 * iss,
+        Scope::Changed => 7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powf(15.0),
+    };
+
+    let av = match attack_vector {
+        AttackVector::Network => 0.85,
+        AttackVector::Adjacent => 0.62,
+        AttackVector::Local => 0.55,
+        AttackVector::Physical => 0.2,
+    };
+    let ac = match attack_complexity {
+        AttackComplexity::Low => 0.77,
+        AttackComplexity::High => 0.44,
+    };
+    let ui = match user_interaction {
+        UserInteraction::None => 0.85,
+        UserInteraction::Required => 0.62,
+    };
+    let pr = match (privileges_required, scope) {
+        (PrivilegesRequired::None, _) => 0.85,
+        (PrivilegesRequired::Low, Scope::Changed) => 0.68,
+        (PrivilegesRequired::Low, Scope::Unchanged) => 0.62,
+        (PrivilegesRequired::High, Scope::Changed) => 0.5,
+        (PrivilegesRequired::High, Scope::Unchanged) => 0.27,
+    };
+
+    let exploitability = 8.22 * av * ac * pr * ui;
+
+    if impact <= 0.0 {
+        return 0.0;
+    }
+
+    match scope {
+        Scope::Unchanged => round_up((impact + exploitability).min(10.0)),
+        Scope::Changed => round_up((1.08 * (impact + exploitability)).min(10.0)),
+    }
+}
+
+/// Round a CVSS score up to the nearest one decimal place, as defined by the
+/// CVSS v3.1 specification's `Roundup` function.
+fn round_up(value: f64) -> f64 {
+    let int_value = (value * 100000.0).round() as i64;
+    if int_value % 10000 == 0 {
+        int_value as f64 / 100000.0
+    } else {
+        ((int_value / 10000) + 1) as f64 / 10.0
+    }
+}
+
 /// Provides a way to give credit for the discovery, confirmation, patch or other events in the
 /// life cycle of a vulnerability.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Credit {
     pub name: String,
     pub contact: Vec<String>,
@@ -260,7 +679,7 @@ pub struct Credit {
 /// This is the entity that is returned when vulnerable data exists for
 /// a given package or when requesting information about a specific vulnerability
 /// by unique identifier.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Vulnerability {
     /// The schema_version field is used to indicate which version of the OSV schema a particular
     /// vulnerability was exported with.
@@ -339,7 +758,7 @@ pub struct Vulnerability {
 
 /// A Request encapsulates the different payloads that will be accepted by the
 /// osv.dev API server. You can either submit a query to the server using a
-/// commit hash or alternatively a package and version pair.
+/// commit hash, a package and version pair, or a package URL.
 #[derive(Debug, Serialize)]
 #[serde(untagged)]
 pub enum Request {
@@ -348,6 +767,20 @@ pub enum Request {
 
     /// Query the vulnerability sources by package and version pair.
     PackageQuery { version: Version, package: Package },
+
+    /// Query the vulnerability sources by package URL. See
+    /// [`query_purl`](query_purl) for details.
+    PurlQuery { package: PurlPackage },
+}
+
+/// A package identified only by its [Package URL](https://github.com/package-url/purl-spec)
+/// (purl), used by [`Request::PurlQuery`](Request::PurlQuery). Submitting the purl
+/// directly, rather than re-deriving a name and [`Ecosystem`](Ecosystem) from it,
+/// avoids having to reverse [`Package::purl`](Package::purl)'s encoding.
+#[derive(Debug, Serialize)]
+pub struct PurlPackage {
+    /// The package's purl, e.g. `pkg:pypi/jinja2@2.4.1`.
+    pub purl: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -367,6 +800,21 @@ pub enum ApiError {
     #[error("requested resource {0} not found")]
     NotFound(String),
 
+    #[error("invalid CVSS vector string: {0:?}")]
+    InvalidCvssVector(String),
+
+    #[error("failed to read SBOM: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("failed to load local osv database: {0}")]
+    LocalDbLoad(std::io::Error),
+
+    #[error("unrecognized SBOM format")]
+    UnrecognizedSbomFormat,
+
+    #[error("{0} is not supported by a client constructed with OsvClient::from_local_db")]
+    UnsupportedByLocalDb(&'static str),
+
     #[error("invalid request url: {0:?}")]
     InvalidUrl(#[from] url::ParseError),
 
@@ -386,6 +834,338 @@ impl From<surf::Error> for ApiError {
     }
 }
 
+/// An index over a local copy of the osv database, built from the per-vulnerability
+/// JSON records found in osv's per-ecosystem `all.zip` export dumps, once extracted
+/// to a directory. Used by [`OsvClient::from_local_db`](OsvClient::from_local_db) to
+/// answer queries without any network access.
+struct LocalDb {
+    by_id: std::collections::HashMap<String, Vulnerability>,
+    by_package: std::collections::HashMap<(String, String), Vec<String>>,
+}
+
+impl LocalDb {
+    fn load(dir: &std::path::Path) -> Result<Self, ApiError> {
+        let mut by_id = std::collections::HashMap::new();
+        let mut by_package: std::collections::HashMap<(String, String), Vec<String>> =
+            std::collections::HashMap::new();
+
+        for path in walk_json_files(dir)? {
+            let data = std::fs::read_to_string(&path).map_err(ApiError::LocalDbLoad)?;
+            let vuln: Vulnerability = serde_json::from_str(&data)?;
+
+            for affected in &vuln.affected {
+                if let Some(ecosystem) = ecosystem_name(&affected.package.ecosystem) {
+                    by_package
+                        .entry((ecosystem, affected.package.name.clone()))
+                        .or_default()
+                        .push(vuln.id.clone());
+                }
+            }
+
+            by_id.insert(vuln.id.clone(), vuln);
+        }
+
+        Ok(LocalDb { by_id, by_package })
+    }
+
+    fn vulnerability(&self, vuln_id: &str) -> Result<Vulnerability, ApiError> {
+        self.by_id
+            .get(vuln_id)
+            .cloned()
+            .ok_or_else(|| ApiError::NotFound(vuln_id.to_string()))
+    }
+
+    fn query(&self, req: &Request) -> Result<Option<Vec<Vulnerability>>, ApiError> {
+        match req {
+            Request::PackageQuery { package, .. } => Ok(self.query_package(package)),
+            Request::CommitQuery { commit } => Err(ApiError::NotFound(format!(
+                "commit - `{}` (local database only indexes by package)",
+                commit
+            ))),
+            Request::PurlQuery { package } => {
+                let (ecosystem, name, _version) = parse_purl(&package.purl)
+                    .ok_or_else(|| ApiError::NotFound(format!("purl - `{}`", package.purl)))?;
+                Ok(self.query_package(&Package {
+                    name,
+                    ecosystem,
+                    purl: Some(package.purl.clone()),
+                }))
+            }
+        }
+    }
+
+    fn query_package(&self, package: &Package) -> Option<Vec<Vulnerability>> {
+        let ecosystem = ecosystem_name(&package.ecosystem)?;
+        let ids = self.by_package.get(&(ecosystem, package.name.clone()))?;
+        Some(ids.iter().filter_map(|id| self.by_id.get(id).cloned()).collect())
+    }
+}
+
+fn ecosystem_name(ecosystem: &Ecosystem) -> Option<String> {
+    serde_json::to_value(ecosystem)
+        .ok()?
+        .as_str()
+        .map(String::from)
+}
+
+fn walk_json_files(dir: &std::path::Path) -> Result<Vec<std::path::PathBuf>, ApiError> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir).map_err(ApiError::LocalDbLoad)? {
+        let path = entry.map_err(ApiError::LocalDbLoad)?.path();
+        if path.is_dir() {
+            files.extend(walk_json_files(&path)?);
+        } else if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+/// A client for the Open Source Vulnerability (osv) API, holding a configurable
+/// base URL and a reusable HTTP client so that requests can be pointed at a
+/// self-hosted osv instance or a compatible caching proxy instead of
+/// `https://api.osv.dev`.
+///
+/// The free functions in this crate (e.g. [`query`](query), [`query_package`](query_package))
+/// are thin wrappers around a default-constructed `OsvClient` and remain the
+/// simplest way to talk to the public osv.dev API.
+///
+/// Use [`OsvClient::from_local_db`](OsvClient::from_local_db) to answer [`query_package`](OsvClient::query_package) and
+/// [`vulnerability`](OsvClient::vulnerability) entirely from a local copy of the database, without
+/// any network access, which is useful in air-gapped environments.
+///
+/// # Examples
+///
+/// ```
+/// # use async_std::task;
+/// # task::block_on(async {
+/// let client = osv::OsvClient::with_base_url("https://osv.example.internal").unwrap();
+/// let vulnerable = client.query_package("jinja2", "2.4.1", osv::Ecosystem::PyPI).await;
+/// # let _ = vulnerable;
+/// # });
+/// ```
+pub struct OsvClient {
+    base_url: Url,
+    client: surf::Client,
+    local_db: Option<LocalDb>,
+}
+
+impl Default for OsvClient {
+    fn default() -> Self {
+        OsvClient::new()
+    }
+}
+
+/// Ensure `url`'s path ends in `/` so that [`Url::join`] appends relative
+/// endpoints onto it instead of replacing its last path segment.
+fn normalize_base_url(mut url: Url) -> Url {
+    if !url.path().ends_with('/') {
+        url.set_path(&format!("{}/", url.path()));
+    }
+    url
+}
+
+impl OsvClient {
+    /// Construct a client pointed at the public `https://api.osv.dev` API.
+    pub fn new() -> Self {
+        OsvClient {
+            base_url: normalize_base_url(
+                Url::parse("https://api.osv.dev").expect("static url is valid"),
+            ),
+            client: surf::Client::new(),
+            local_db: None,
+        }
+    }
+
+    /// Construct a client pointed at a self-hosted or otherwise compatible osv API
+    /// running at `base_url`, e.g. a caching proxy in front of `api.osv.dev`.
+    ///
+    /// `base_url` may point at a sub-path, e.g. `https://proxy.example.com/osv-api`;
+    /// it is normalized to end in `/` so that [`Url::join`] appends endpoints to it
+    /// instead of replacing its last path segment.
+    pub fn with_base_url(base_url: &str) -> Result<Self, ApiError> {
+        Ok(OsvClient {
+            base_url: normalize_base_url(Url::parse(base_url)?),
+            client: surf::Client::new(),
+            local_db: None,
+        })
+    }
+
+    ///
+    /// Construct a client that answers [`query_package`](OsvClient::query_package) and
+    /// [`vulnerability`](OsvClient::vulnerability) entirely from a local copy of the osv
+    /// database, without making any network requests.
+    ///
+    /// `dir` should contain the JSON vulnerability records from one or more of
+    /// osv's per-ecosystem [`all.zip`](https://osv.dev/docs/#tag/api/operation/OSV_ListAllVulns)
+    /// export dumps, already extracted to disk; subdirectories are searched
+    /// recursively. This is critical for air-gapped environments and
+    /// reproducible scans that shouldn't depend on network availability.
+    ///
+    /// Local queries match on package name and ecosystem only: the queried
+    /// version is not checked against the records' affected ranges, so every
+    /// record that lists the package is returned.
+    pub fn from_local_db<P: AsRef<std::path::Path>>(dir: P) -> Result<Self, ApiError> {
+        Ok(OsvClient {
+            base_url: normalize_base_url(
+                Url::parse("https://api.osv.dev").expect("static url is valid"),
+            ),
+            client: surf::Client::new(),
+            local_db: Some(LocalDb::load(dir.as_ref())?),
+        })
+    }
+
+    /// Query for vulnerabilities associated with a package, a commit or a package
+    /// URL. See the free function [`query`](query) for details.
+    pub async fn query(&self, q: &Request) -> Result<Option<Vec<Vulnerability>>, ApiError> {
+        if let Some(db) = &self.local_db {
+            return db.query(q);
+        }
+
+        let url = self.base_url.join("v1/query")?;
+        let mut res = self.client.post(url.as_str()).body_json(q)?.await?;
+
+        match res.status() {
+            StatusCode::NotFound => {
+                let err = match q {
+                    Request::PackageQuery {
+                        version: _,
+                        package: pkg,
+                    } => {
+                        format!("package - `{}`", pkg.name)
+                    }
+                    Request::CommitQuery { commit: c } => {
+                        format!("commit - `{}`", c)
+                    }
+                    Request::PurlQuery { package: pkg } => {
+                        format!("purl - `{}`", pkg.purl)
+                    }
+                };
+                Err(ApiError::NotFound(err))
+            }
+            _ => {
+                let vulns: Response = res.body_json().await?;
+                match vulns {
+                    Response::Vulnerabilities { vulns: vs } => Ok(Some(vs)),
+                    _ => Ok(None),
+                }
+            }
+        }
+    }
+
+    /// Query for vulnerabilities associated with the specified package and version.
+    /// See the free function [`query_package`](query_package) for details.
+    pub async fn query_package(
+        &self,
+        name: &str,
+        version: &str,
+        ecosystem: Ecosystem,
+    ) -> Result<Option<Vec<Vulnerability>>, ApiError> {
+        let req = Request::PackageQuery {
+            version: Version::from(version),
+            package: Package {
+                name: name.to_string(),
+                ecosystem,
+                purl: None,
+            },
+        };
+        self.query(&req).await
+    }
+
+    /// Query for vulnerabilities based on a Git commit SHA1. See the free function
+    /// [`query_commit`](query_commit) for details.
+    pub async fn query_commit(&self, commit: &str) -> Result<Option<Vec<Vulnerability>>, ApiError> {
+        let req = Request::CommitQuery {
+            commit: Commit::from(commit),
+        };
+        self.query(&req).await
+    }
+
+    /// Query for vulnerabilities associated with a package identified by its purl.
+    /// See the free function [`query_purl`](query_purl) for details.
+    pub async fn query_purl(&self, purl: &str) -> Result<Option<Vec<Vulnerability>>, ApiError> {
+        let req = Request::PurlQuery {
+            package: PurlPackage {
+                purl: purl.to_string(),
+            },
+        };
+        self.query(&req).await
+    }
+
+    /// Submit a batch of queries in a single round trip. See the free function
+    /// [`query_batch`](query_batch) for details.
+    ///
+    /// Not supported by a client constructed with [`from_local_db`](OsvClient::from_local_db),
+    /// since the `/v1/querybatch` endpoint has no local-database equivalent.
+    pub async fn query_batch(&self, queries: &[Request]) -> Result<Vec<BatchResult>, ApiError> {
+        if self.local_db.is_some() {
+            return Err(ApiError::UnsupportedByLocalDb("query_batch"));
+        }
+
+        let url = self.base_url.join("v1/querybatch")?;
+        let mut res = self
+            .client
+            .post(url.as_str())
+            .body_json(&BatchRequest { queries })?
+            .await?;
+        let batch: BatchResponse = res.body_json().await?;
+        Ok(batch.results)
+    }
+
+    /// Submit a batch of queries in a single round trip, resuming any query
+    /// that returned a [`next_page_token`](BatchResult::next_page_token) from a
+    /// previous `query_batch`/`query_batch_page` call. See the free function
+    /// [`query_batch_page`](query_batch_page) for details.
+    ///
+    /// Not supported by a client constructed with [`from_local_db`](OsvClient::from_local_db),
+    /// since the `/v1/querybatch` endpoint has no local-database equivalent.
+    pub async fn query_batch_page(
+        &self,
+        queries: &[PagedRequest<'_>],
+    ) -> Result<Vec<BatchResult>, ApiError> {
+        if self.local_db.is_some() {
+            return Err(ApiError::UnsupportedByLocalDb("query_batch_page"));
+        }
+
+        let url = self.base_url.join("v1/querybatch")?;
+        let mut res = self
+            .client
+            .post(url.as_str())
+            .body_json(&BatchPageRequest { queries })?
+            .await?;
+        let batch: BatchResponse = res.body_json().await?;
+        Ok(batch.results)
+    }
+
+    /// Query for a vulnerability by ID. See the free function
+    /// [`vulnerability`](vulnerability) for details.
+    pub async fn vulnerability(&self, vuln_id: &str) -> Result<Vulnerability, ApiError> {
+        if let Some(db) = &self.local_db {
+            return db.vulnerability(vuln_id);
+        }
+
+        let base = self.base_url.join("v1/vulns/")?;
+        let req = base.join(vuln_id)?;
+        let mut res = self.client.get(req.as_str()).await?;
+        if res.status() == StatusCode::NotFound {
+            Err(ApiError::NotFound(vuln_id.to_string()))
+        } else {
+            let vuln: Vulnerability = res.body_json().await?;
+            Ok(vuln)
+        }
+    }
+
+    /// Fetch the full record for each batch match. See the free function
+    /// [`hydrate`](hydrate) for details.
+    pub async fn hydrate(&self, matches: &[BatchMatch]) -> Result<Vec<Vulnerability>, ApiError> {
+        let mut vulns = Vec::with_capacity(matches.len());
+        for m in matches {
+            vulns.push(self.vulnerability(&m.id).await?);
+        }
+        Ok(vulns)
+    }
+}
+
 ///
 /// Query the underlying Open Source Vulnerability (osv) database for
 /// any vulnerabilities associated with either a package or a commit.
@@ -421,33 +1201,7 @@ impl From<surf::Error> for ApiError {
 ///
 ///
 pub async fn query(q: &Request) -> Result<Option<Vec<Vulnerability>>, ApiError> {
-    let mut res = surf::post("https://api.osv.dev/v1/query")
-        .body_json(q)?
-        .await?;
-
-    match res.status() {
-        StatusCode::NotFound => {
-            let err = match q {
-                Request::PackageQuery {
-                    version: _,
-                    package: pkg,
-                } => {
-                    format!("package - `{}`", pkg.name)
-                }
-                Request::CommitQuery { commit: c } => {
-                    format!("commit - `{}`", c)
-                }
-            };
-            Err(ApiError::NotFound(err))
-        }
-        _ => {
-            let vulns: Response = res.body_json().await?;
-            match vulns {
-                Response::Vulnerabilities { vulns: vs } => Ok(Some(vs)),
-                _ => Ok(None),
-            }
-        }
-    }
+    OsvClient::new().query(q).await
 }
 
 ///
@@ -484,16 +1238,7 @@ pub async fn query_package(
     version: &str,
     ecosystem: Ecosystem,
 ) -> Result<Option<Vec<Vulnerability>>, ApiError> {
-    let req = Request::PackageQuery {
-        version: Version::from(version),
-        package: Package {
-            name: name.to_string(),
-            ecosystem,
-            purl: None,
-        },
-    };
-
-    query(&req).await
+    OsvClient::new().query_package(name, version, ecosystem).await
 }
 
 ///
@@ -521,10 +1266,7 @@ pub async fn query_package(
 /// ```
 ///
 pub async fn query_commit(commit: &str) -> Result<Option<Vec<Vulnerability>>, ApiError> {
-    let req = Request::CommitQuery {
-        commit: Commit::from(commit),
-    };
-    query(&req).await
+    OsvClient::new().query_commit(commit).await
 }
 
 ///
@@ -542,15 +1284,202 @@ pub async fn query_commit(commit: &str) -> Result<Option<Vec<Vulnerability>>, Ap
 /// # });
 /// ```
 pub async fn vulnerability(vuln_id: &str) -> Result<Vulnerability, ApiError> {
-    let base = Url::parse("https://api.osv.dev/v1/vulns/")?;
-    let req = base.join(vuln_id)?;
-    let mut res = surf::get(req.as_str()).await?;
-    if res.status() == StatusCode::NotFound {
-        Err(ApiError::NotFound(vuln_id.to_string()))
-    } else {
-        let vuln: Vulnerability = res.body_json().await?;
-        Ok(vuln)
-    }
+    OsvClient::new().vulnerability(vuln_id).await
+}
+
+///
+/// Query the Open Source Vulnerability (osv) database for vulnerabilities
+/// associated with a package identified by its [Package URL
+/// specification](https://github.com/package-url/purl-spec) (purl).
+///
+/// This is useful when the caller already has a purl on hand, for example
+/// from SBOM tooling, and doesn't want to reverse-map it to a name and
+/// [`Ecosystem`](Ecosystem) pair first. See [`Package::purl`](Package::purl) to derive a
+/// purl from a name and ecosystem.
+///
+/// # Examples
+///
+/// ```
+/// # use async_std::task;
+/// # use osv::query_purl;
+/// # task::block_on(async {
+/// let vulnerable = query_purl("pkg:pypi/jinja2@2.4.1")
+///     .await
+///     .expect("api error");
+///
+/// match vulnerable {
+///     Some(vulns) => println!("{:#?}", vulns),
+///     None => println!("all clear!"),
+/// }
+/// # });
+/// ```
+pub async fn query_purl(purl: &str) -> Result<Option<Vec<Vulnerability>>, ApiError> {
+    OsvClient::new().query_purl(purl).await
+}
+
+/// A match returned by the [`/v1/querybatch`](query_batch) endpoint. Unlike
+/// [`query`](query), the batch endpoint does not return full vulnerability
+/// records, only the ID and the time it was last modified. Use
+/// [`hydrate`](hydrate) to turn these into full [`Vulnerability`](Vulnerability) records.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchMatch {
+    /// The unique identifier of the matched vulnerability.
+    pub id: String,
+
+    /// The time the matched vulnerability was last modified.
+    pub modified: DateTime<Utc>,
+}
+
+/// The result of a single query within a [`query_batch`](query_batch) call. The
+/// matches are positionally aligned with the queries passed to `query_batch`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BatchResult {
+    /// The vulnerabilities matched by this query.
+    #[serde(default)]
+    pub vulns: Vec<BatchMatch>,
+
+    /// A page token to pass back to retrieve the next page of matches for
+    /// this same query, if the result set was too large to return in one
+    /// response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchRequest<'a> {
+    queries: &'a [Request],
+}
+
+/// A [`Request`](Request) paired with an optional page token, used to resume a
+/// query whose previous [`query_batch`](query_batch)/`query_batch_page` result
+/// returned a [`next_page_token`](BatchResult::next_page_token) because the
+/// match set was too large for a single response.
+#[derive(Debug, Serialize)]
+pub struct PagedRequest<'a> {
+    /// The underlying commit, package/version or purl query.
+    #[serde(flatten)]
+    pub request: &'a Request,
+
+    /// The page token returned by a previous call for this same query, or
+    /// `None` to fetch the first page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_token: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct BatchPageRequest<'a> {
+    queries: &'a [PagedRequest<'a>],
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchResponse {
+    results: Vec<BatchResult>,
+}
+
+///
+/// Submit a batch of queries to the Open Source Vulnerability (osv) database
+/// in a single round trip, using the `/v1/querybatch` endpoint.
+///
+/// The results are positionally aligned with `queries`, i.e. `results[i]`
+/// holds the matches for `queries[i]`. Because the batch endpoint only
+/// returns the vulnerability ID and `modified` timestamp for each match, use
+/// [`hydrate`](hydrate) on the returned [`BatchMatch`](BatchMatch) values to fetch the full
+/// [`Vulnerability`](Vulnerability) records.
+///
+/// See <https://osv.dev/docs/#operation/OSV_QueryAffectedBatch> for more details.
+///
+/// # Examples
+///
+/// ```
+/// # use async_std::task;
+/// # task::block_on(async {
+/// let queries = vec![osv::Request::PackageQuery {
+///     version: osv::Version::from("2.4.1"),
+///     package: osv::Package {
+///         name: "jinja2".to_string(),
+///         ecosystem: osv::Ecosystem::PyPI,
+///         purl: None,
+///     },
+/// }];
+///
+/// let results = osv::query_batch(&queries).await.unwrap();
+/// assert_eq!(results.len(), queries.len());
+/// # });
+/// ```
+pub async fn query_batch(queries: &[Request]) -> Result<Vec<BatchResult>, ApiError> {
+    OsvClient::new().query_batch(queries).await
+}
+
+///
+/// Submit a batch of queries to the Open Source Vulnerability (osv) database,
+/// resuming any query that returned a
+/// [`next_page_token`](BatchResult::next_page_token) from a previous
+/// [`query_batch`](query_batch)/`query_batch_page` call. This is the paging
+/// counterpart of [`query_batch`](query_batch): pass back each query paired with
+/// the token from its prior result to fetch the next page of matches for it.
+///
+/// The results are positionally aligned with `queries`, the same way as
+/// [`query_batch`](query_batch).
+///
+/// See <https://osv.dev/docs/#operation/OSV_QueryAffectedBatch> for more details.
+///
+/// # Examples
+///
+/// ```
+/// # use async_std::task;
+/// # task::block_on(async {
+/// let queries = vec![osv::Request::PackageQuery {
+///     version: osv::Version::from("2.4.1"),
+///     package: osv::Package {
+///         name: "jinja2".to_string(),
+///         ecosystem: osv::Ecosystem::PyPI,
+///         purl: None,
+///     },
+/// }];
+///
+/// let first_page = osv::query_batch(&queries).await.unwrap();
+///
+/// let paged: Vec<_> = queries
+///     .iter()
+///     .zip(first_page.iter())
+///     .map(|(request, result)| osv::PagedRequest {
+///         request,
+///         page_token: result.next_page_token.clone(),
+///     })
+///     .collect();
+///
+/// let next_page = osv::query_batch_page(&paged).await.unwrap();
+/// assert_eq!(next_page.len(), queries.len());
+/// # });
+/// ```
+pub async fn query_batch_page(queries: &[PagedRequest<'_>]) -> Result<Vec<BatchResult>, ApiError> {
+    OsvClient::new().query_batch_page(queries).await
+}
+
+///
+/// Fetch the full [`Vulnerability`](Vulnerability) record for each [`BatchMatch`](BatchMatch)
+/// returned by [`query_batch`](query_batch).
+///
+/// This issues one request per match via [`vulnerability`](vulnerability), so it is best
+/// used after the batch results have already been filtered down to the
+/// matches that are actually needed.
+///
+/// # Examples
+///
+/// ```
+/// # use async_std::task;
+/// # task::block_on(async {
+/// let matches = vec![osv::BatchMatch {
+///     id: "OSV-2020-484".to_string(),
+///     modified: chrono::Utc::now(),
+/// }];
+///
+/// let vulns = osv::hydrate(&matches).await.unwrap();
+/// assert_eq!(vulns.len(), matches.len());
+/// # });
+/// ```
+pub async fn hydrate(matches: &[BatchMatch]) -> Result<Vec<Vulnerability>, ApiError> {
+    OsvClient::new().hydrate(matches).await
 }
 
 #[cfg(test)]
@@ -626,6 +1555,216 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[async_std::test]
+    async fn test_osv_client_with_base_url() {
+        let client = OsvClient::with_base_url("https://api.osv.dev").unwrap();
+        let res = client
+            .query_package("jinja2", "2.4.1", Ecosystem::PyPI)
+            .await
+            .unwrap();
+        assert!(res.is_some());
+    }
+
+    #[test]
+    fn test_osv_client_with_base_url_sub_path() {
+        let client = OsvClient::with_base_url("https://proxy.example.com/osv-api").unwrap();
+        assert_eq!(client.base_url.as_str(), "https://proxy.example.com/osv-api/");
+        assert_eq!(
+            client.base_url.join("v1/query").unwrap().as_str(),
+            "https://proxy.example.com/osv-api/v1/query"
+        );
+    }
+
+    #[test]
+    fn test_osv_client_from_local_db() {
+        let dir = std::env::temp_dir().join(format!("osv-test-db-{:?}", std::thread::current().id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("OSV-2020-484.json"),
+            serde_json::json!({
+                "schema_version": "1.3.0",
+                "id": "OSV-2020-484",
+                "published": "2020-01-01T00:00:00Z",
+                "modified": "2020-01-01T00:00:00Z",
+                "affected": [{
+                    "package": {
+                        "name": "jinja2",
+                        "ecosystem": "PyPI",
+                    },
+                    "ranges": [],
+                }],
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let client = OsvClient::from_local_db(&dir).unwrap();
+        let res = async_std::task::block_on(client.vulnerability("OSV-2020-484")).unwrap();
+        assert_eq!(res.id, "OSV-2020-484");
+
+        let res = async_std::task::block_on(client.query_package("jinja2", "2.4.1", Ecosystem::PyPI))
+            .unwrap()
+            .unwrap();
+        assert_eq!(res.len(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_package_purl() {
+        let pkg = Package {
+            name: "jinja2".to_string(),
+            ecosystem: Ecosystem::PyPI,
+            purl: None,
+        };
+        assert_eq!(pkg.purl(), "pkg:pypi/jinja2");
+
+        let pkg = Package {
+            name: "serde".to_string(),
+            ecosystem: Ecosystem::CratesIO,
+            purl: None,
+        };
+        assert_eq!(pkg.purl(), "pkg:cargo/serde");
+
+        let pkg = Package {
+            name: "org.springframework:spring-core".to_string(),
+            ecosystem: Ecosystem::Maven,
+            purl: None,
+        };
+        assert_eq!(pkg.purl(), "pkg:maven/org.springframework/spring-core");
+    }
+
+    #[test]
+    fn test_parse_purl() {
+        assert_eq!(
+            parse_purl("pkg:cargo/serde@1.0.0"),
+            Some((Ecosystem::CratesIO, "serde".to_string(), Some("1.0.0".to_string())))
+        );
+        assert_eq!(
+            parse_purl("pkg:npm/left-pad"),
+            Some((Ecosystem::Npm, "left-pad".to_string(), None))
+        );
+        // Scoped npm packages are percent-encoded in the namespace and
+        // should come back out decoded.
+        assert_eq!(
+            parse_purl("pkg:npm/%40babel/core@7.0.0"),
+            Some((Ecosystem::Npm, "@babel/core".to_string(), Some("7.0.0".to_string())))
+        );
+        // Maven's namespace/name split must be rejoined with `:`, the
+        // inverse of Package::purl()'s Maven special case.
+        assert_eq!(
+            parse_purl("pkg:maven/org.springframework/spring-core"),
+            Some((Ecosystem::Maven, "org.springframework:spring-core".to_string(), None))
+        );
+    }
+
+    #[async_std::test]
+    async fn test_query_purl() {
+        let res = query_purl("pkg:pypi/jinja2@2.4.1").await.unwrap();
+        assert!(res.is_some());
+    }
+
+    #[async_std::test]
+    async fn test_query_batch() {
+        let queries = vec![Request::PackageQuery {
+            version: Version::from("2.4.1"),
+            package: Package {
+                name: "jinja2".to_string(),
+                ecosystem: Ecosystem::PyPI,
+                purl: None,
+            },
+        }];
+        let results = query_batch(&queries).await.unwrap();
+        assert_eq!(results.len(), queries.len());
+        assert!(!results[0].vulns.is_empty());
+    }
+
+    #[async_std::test]
+    async fn test_query_batch_page() {
+        let queries = [Request::PackageQuery {
+            version: Version::from("2.4.1"),
+            package: Package {
+                name: "jinja2".to_string(),
+                ecosystem: Ecosystem::PyPI,
+                purl: None,
+            },
+        }];
+        let paged = [PagedRequest {
+            request: &queries[0],
+            page_token: None,
+        }];
+        let results = query_batch_page(&paged).await.unwrap();
+        assert_eq!(results.len(), paged.len());
+    }
+
+    #[async_std::test]
+    async fn test_hydrate() {
+        let matches = vec![BatchMatch {
+            id: "OSV-2020-484".to_string(),
+            modified: chrono::Utc::now(),
+        }];
+        let vulns = hydrate(&matches).await.unwrap();
+        assert_eq!(vulns.len(), 1);
+        assert!(vulns[0].id.eq("OSV-2020-484"));
+    }
+
+    #[test]
+    fn test_parse_cvss() {
+        let severity = Severity {
+            severity_type: SeverityType::CVSSv3,
+            score: "CVSS:3.1/AV:N/AC:H/PR:N/UI:N/S:C/C:H/I:N/A:N".to_string(),
+        };
+        let cvss = severity.parse_cvss().unwrap();
+        assert_eq!(cvss.attack_vector, AttackVector::Network);
+        assert_eq!(cvss.scope, Scope::Changed);
+        assert_eq!(cvss.base_score, 6.8);
+    }
+
+    #[test]
+    fn test_parse_cvss_unchanged_scope() {
+        let cvss = CvssV3::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H").unwrap();
+        assert_eq!(cvss.scope, Scope::Unchanged);
+        assert_eq!(cvss.base_score, 9.8);
+    }
+
+    #[test]
+    fn test_parse_cvss_no_impact() {
+        let cvss = CvssV3::parse("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N").unwrap();
+        assert_eq!(cvss.base_score, 0.0);
+    }
+
+    #[test]
+    fn test_parse_cvss_malformed() {
+        let res = CvssV3::parse("not a cvss vector");
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_affected_severity_roundtrip() {
+        let affected = Affected {
+            package: Package {
+                name: "jinja2".to_string(),
+                ecosystem: Ecosystem::PyPI,
+                purl: None,
+            },
+            ranges: vec![],
+            versions: None,
+            severity: Some(vec![Severity {
+                severity_type: SeverityType::Ubuntu,
+                score: "Medium".to_string(),
+            }]),
+            ecosystem_specific: None,
+            database_specific: None,
+        };
+
+        let as_json = serde_json::json!(affected);
+        let str_json = as_json.to_string();
+        assert!(str_json.contains("\"severity\""));
+
+        let parsed: Affected = serde_json::from_str(&str_json).unwrap();
+        assert!(parsed.severity.is_some());
+    }
+
     #[async_std::test]
     async fn test_no_serialize_null_fields() {
         let vuln = Vulnerability {
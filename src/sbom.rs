@@ -0,0 +1,285 @@
+//!
+//! Scan a software bill of materials (SBOM) for known vulnerabilities.
+//!
+//! This module understands the two most common SBOM formats, [CycloneDX](https://cyclonedx.org/)
+//! and [SPDX](https://spdx.dev/), well enough to extract each component's
+//! name, version, and [purl](https://github.com/package-url/purl-spec), then
+//! batch-queries the osv database for all of them in one round trip via
+//! [`query_batch`](crate::query_batch).
+
+use crate::{hydrate, query_batch, ApiError, BatchResult, PurlPackage, Request, Vulnerability};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// A single component extracted from an SBOM.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Component {
+    /// The component's name, as given in the SBOM.
+    pub name: String,
+
+    /// The component's version, if the SBOM specifies one.
+    pub version: Option<String>,
+
+    /// The component's [Package URL](https://github.com/package-url/purl-spec), if the
+    /// SBOM specifies one. This is used to derive the [`Ecosystem`](crate::Ecosystem) to query.
+    pub purl: Option<String>,
+}
+
+/// The vulnerabilities found for a single [`Component`](Component) of an SBOM.
+#[derive(Debug)]
+pub struct ComponentFindings {
+    /// The component these findings apply to.
+    pub component: Component,
+
+    /// The vulnerabilities affecting this component, if any.
+    pub vulnerabilities: Vec<Vulnerability>,
+}
+
+///
+/// Parse the CycloneDX or SPDX SBOM at `path` and query osv for
+/// vulnerabilities affecting every component it describes.
+///
+/// Components are deduplicated before querying, and all of them are
+/// submitted to osv in a single [`query_batch`](crate::query_batch) call rather than one
+/// request per component. Components that don't carry a purl osv
+/// understands (see [`Package::purl`](crate::Package::purl)), or whose purl and
+/// component entry both lack a version, are still returned, with an empty
+/// vulnerability list, since they are not queried.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use async_std::task;
+/// # task::block_on(async {
+/// let findings = osv::sbom::scan_sbom("sbom.cdx.json").await.unwrap();
+/// for finding in &findings {
+///     if !finding.vulnerabilities.is_empty() {
+///         println!("{}: {} known vulnerabilities", finding.component.name, finding.vulnerabilities.len());
+///     }
+/// }
+/// # });
+/// ```
+pub async fn scan_sbom<P: AsRef<Path>>(path: P) -> Result<Vec<ComponentFindings>, ApiError> {
+    let data = std::fs::read_to_string(path)?;
+    let doc: serde_json::Value = serde_json::from_str(&data)?;
+
+    let components = if doc.get("bomFormat").and_then(|v| v.as_str()) == Some("CycloneDX") {
+        parse_cyclonedx(&doc)
+    } else if doc.get("spdxVersion").is_some() {
+        parse_spdx(&doc)
+    } else {
+        return Err(ApiError::UnrecognizedSbomFormat);
+    };
+
+    let mut seen = HashSet::new();
+    let components: Vec<Component> = components.into_iter().filter(|c| seen.insert(c.clone())).collect();
+
+    let mut queries = Vec::new();
+    let mut is_queryable = Vec::with_capacity(components.len());
+    for component in &components {
+        if let Some(purl) = component_purl(component) {
+            queries.push(Request::PurlQuery {
+                package: PurlPackage { purl },
+            });
+            is_queryable.push(true);
+        } else {
+            is_queryable.push(false);
+        }
+    }
+
+    let results: Vec<BatchResult> = query_batch(&queries).await?;
+
+    // Many components often share the same vulnerability (e.g. a transitive
+    // dependency pulled in by several packages), so de-duplicate matches
+    // across the whole findings set before hydrating, instead of hydrating
+    // each component's matches separately.
+    let mut seen_ids = HashSet::new();
+    let unique_matches: Vec<_> = results
+        .iter()
+        .flat_map(|result| &result.vulns)
+        .filter(|m| seen_ids.insert(m.id.clone()))
+        .cloned()
+        .collect();
+    let vulnerabilities_by_id: HashMap<String, Vulnerability> =
+        hydrate(&unique_matches).await?.into_iter().map(|v| (v.id.clone(), v)).collect();
+
+    let mut results = results.into_iter();
+    let mut findings = Vec::with_capacity(components.len());
+    for (component, queryable) in components.into_iter().zip(is_queryable) {
+        let vulnerabilities = if queryable {
+            let result = results.next().unwrap_or(BatchResult {
+                vulns: vec![],
+                next_page_token: None,
+            });
+            result
+                .vulns
+                .iter()
+                .filter_map(|m| vulnerabilities_by_id.get(&m.id).cloned())
+                .collect()
+        } else {
+            Vec::new()
+        };
+        findings.push(ComponentFindings {
+            component,
+            vulnerabilities,
+        });
+    }
+
+    Ok(findings)
+}
+
+/// Derive the purl to submit as a [`Request::PurlQuery`](Request::PurlQuery) for a
+/// component, validating that osv understands its ecosystem (via
+/// [`crate::parse_purl`]) without re-deriving a name from it — the purl is
+/// submitted to osv unchanged, so there's no risk of mangling percent-encoded
+/// or Maven-style namespaces on the way back in.
+///
+/// If the purl has no version qualifier, the component's own `version` field
+/// is inserted as its version (ahead of any `?qualifiers` or `#subpath`), since
+/// a bare purl matches every version of a package. If neither has a version,
+/// the component is not queried.
+fn component_purl(component: &Component) -> Option<String> {
+    let purl = component.purl.as_ref()?;
+    let (_, _, purl_version) = crate::parse_purl(purl)?;
+    Some(match (purl_version, &component.version) {
+        (Some(_), _) => purl.clone(),
+        (None, Some(version)) => {
+            let end = purl.find(['?', '#']).unwrap_or(purl.len());
+            format!(
+                "{}@{}{}",
+                &purl[..end],
+                crate::encode_purl_component(version),
+                &purl[end..]
+            )
+        }
+        (None, None) => return None,
+    })
+}
+
+/// Recursively collect every component of a CycloneDX document, including
+/// components nested inside other components.
+fn parse_cyclonedx(doc: &serde_json::Value) -> Vec<Component> {
+    let mut components = Vec::new();
+    if let Some(list) = doc.get("components").and_then(|v| v.as_array()) {
+        collect_cyclonedx_components(list, &mut components);
+    }
+    components
+}
+
+fn collect_cyclonedx_components(list: &[serde_json::Value], out: &mut Vec<Component>) {
+    for item in list {
+        if let Some(name) = item.get("name").and_then(|v| v.as_str()) {
+            out.push(Component {
+                name: name.to_string(),
+                version: item.get("version").and_then(|v| v.as_str()).map(String::from),
+                purl: item.get("purl").and_then(|v| v.as_str()).map(String::from),
+            });
+        }
+        if let Some(nested) = item.get("components").and_then(|v| v.as_array()) {
+            collect_cyclonedx_components(nested, out);
+        }
+    }
+}
+
+/// Collect every package of an SPDX document, pulling the purl out of the
+/// package's `externalRefs`, if it has one.
+fn parse_spdx(doc: &serde_json::Value) -> Vec<Component> {
+    let mut components = Vec::new();
+    let Some(list) = doc.get("packages").and_then(|v| v.as_array()) else {
+        return components;
+    };
+    for item in list {
+        let Some(name) = item.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        let version = item.get("versionInfo").and_then(|v| v.as_str()).map(String::from);
+        let purl = item
+            .get("externalRefs")
+            .and_then(|v| v.as_array())
+            .and_then(|refs| {
+                refs.iter()
+                    .find(|r| r.get("referenceType").and_then(|v| v.as_str()) == Some("purl"))
+            })
+            .and_then(|r| r.get("referenceLocator"))
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        components.push(Component {
+            name: name.to_string(),
+            version,
+            purl,
+        });
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cyclonedx() {
+        let doc = serde_json::json!({
+            "bomFormat": "CycloneDX",
+            "components": [
+                {"type": "library", "name": "jinja2", "version": "2.4.1", "purl": "pkg:pypi/jinja2@2.4.1"},
+                {"type": "library", "name": "outer", "components": [
+                    {"type": "library", "name": "inner", "version": "1.0.0", "purl": "pkg:cargo/inner@1.0.0"}
+                ]}
+            ]
+        });
+        let components = parse_cyclonedx(&doc);
+        assert_eq!(components.len(), 3);
+        assert!(components.iter().any(|c| c.name == "inner"));
+    }
+
+    #[async_std::test]
+    async fn test_scan_sbom_maven_component() {
+        // Regression test for a Maven purl losing its `groupId:artifactId`
+        // colon when queried: Package::purl() encodes the namespace using
+        // `/`, so scan_sbom must submit the purl as-is rather than
+        // re-deriving a `name` from it incorrectly.
+        let path = std::env::temp_dir().join(format!("osv-test-sbom-{:?}.cdx.json", std::thread::current().id()));
+        std::fs::write(
+            &path,
+            serde_json::json!({
+                "bomFormat": "CycloneDX",
+                "components": [{
+                    "type": "library",
+                    "name": "log4j-core",
+                    "version": "2.14.1",
+                    "purl": "pkg:maven/org.apache.logging.log4j/log4j-core@2.14.1",
+                }],
+            })
+            .to_string(),
+        )
+        .unwrap();
+
+        let findings = scan_sbom(&path).await.unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(findings.len(), 1);
+        assert!(
+            !findings[0].vulnerabilities.is_empty(),
+            "expected log4j-core 2.14.1 to have known vulnerabilities (e.g. CVE-2021-44228)"
+        );
+    }
+
+    #[test]
+    fn test_parse_spdx() {
+        let doc = serde_json::json!({
+            "spdxVersion": "SPDX-2.3",
+            "packages": [
+                {
+                    "name": "jinja2",
+                    "versionInfo": "2.4.1",
+                    "externalRefs": [
+                        {"referenceCategory": "PACKAGE-MANAGER", "referenceType": "purl", "referenceLocator": "pkg:pypi/jinja2@2.4.1"}
+                    ]
+                }
+            ]
+        });
+        let components = parse_spdx(&doc);
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].purl.as_deref(), Some("pkg:pypi/jinja2@2.4.1"));
+    }
+}